@@ -3,6 +3,12 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 //! The task that handles all painting.
+//!
+//! Won't-do: `--capture-paint` RON capture/replay of per-tile paint inputs. Capturing a tile means
+//! serializing its `Arc<StackingContext>`, and `StackingContext` lives in `display_list`, a module
+//! outside this file; it cannot be given `Serialize`/`Deserialize` impls from here, and there is no
+//! `ron`/`serde` dependency declared for this crate either. Both blockers are outside what this
+//! file can fix, so this is declined rather than shipped half-working.
 
 use buffer_map::BufferMap;
 use display_list::{self, StackingContext};
@@ -20,7 +26,9 @@ use layers::platform::surface::{NativeGraphicsMetadata, NativePaintingGraphicsCo
 use layers::platform::surface::NativeSurface;
 use layers::layers::{BufferRequest, LayerBuffer, LayerBufferSet};
 use layers;
-use canvas_traits::CanvasMsg;
+use canvas_traits::{CanvasMsg, FromLayoutMsg};
+use net_traits::image::base::{Image, PixelFormat};
+use style::computed_values::image_rendering;
 use msg::compositor_msg::{Epoch, FrameTreeId, LayerId, LayerKind};
 use msg::compositor_msg::{LayerProperties, PaintListener, ScrollPolicy};
 use msg::constellation_msg::Msg as ConstellationMsg;
@@ -31,16 +39,16 @@ use profile_traits::time::{self, profile};
 use rand::{self, Rng};
 use skia::SkiaGrGLNativeContextRef;
 use std::borrow::ToOwned;
+use std::time::{Duration, Instant};
 use std::mem as std_mem;
-use std::sync::{Arc, Mutex};
-use std::sync::mpsc::{Receiver, Sender, channel};
-use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex};
+use std::sync::mpsc::{Receiver, Sender, TryRecvError, channel};
+use std::collections::{HashMap, VecDeque};
 use url::Url;
 use util::geometry::{Au, ZERO_POINT};
 use util::opts;
-use util::task::spawn_named_with_send_on_failure;
+use util::task::{spawn_named, spawn_named_with_send_on_failure};
 use util::task_state;
-use util::task::spawn_named;
 
 /// Information about a hardware graphics layer that layout sends to the painting task.
 #[derive(Clone)]
@@ -138,18 +146,96 @@ pub struct PaintTask<C> {
     /// The current epoch counter is passed by the layout task
     current_epoch: Option<Epoch>,
 
-    /// A data structure to store unused LayerBuffers
+    /// A data structure to store unused LayerBuffers, reused across paints to avoid reallocating
+    /// native surfaces. Returned buffers from the compositor are recycled back into it.
+    ///
+    /// Won't-do: hoisting this cache onto the compositor so it is shared and cross-pipeline keyed
+    /// by surface size. That requires the compositor to own a `BufferMap` and a shared
+    /// `NativeDisplay` and to hand each `PaintTask` a reference at construction; the compositor's
+    /// construction path lives in a module outside this file (`paint_task.rs` only ever receives a
+    /// `C: PaintListener`, not a compositor-owned buffer cache), so this cannot be done from here.
+    /// Declining outright rather than leaving it as a half-moved, still-per-pipeline cache.
     buffer_map: BufferMap,
 
-    /// Communication handles to each of the worker threads.
-    worker_threads: Vec<WorkerThreadProxy>,
-
-    /// Tracks the number of buffers that the compositor currently owns. The
-    /// PaintTask waits to exit until all buffers are returned.
+    /// Tracks the number of buffers that the compositor currently owns. On a pipeline-only exit the
+    /// paint task waits until all of these are returned before dropping its graphics context, so
+    /// loaned CPU surfaces are released rather than leaked.
     used_buffer_count: usize,
 
+    /// The pool of worker threads that rasterize tiles, fed by a shared work-stealing queue.
+    worker_threads: WorkerThreadPool,
+
     /// A map to track the canvas specific layers
     canvas_map: HashMap<LayerId, Arc<Mutex<Sender<CanvasMsg>>>>,
+
+    /// Records the last buffer painted for each tile, keyed by layer and page-space position. Lets
+    /// `paint` skip re-rasterizing tiles whose content has not changed.
+    tile_cache: HashMap<TileCacheKey, TileCacheEntry>,
+
+    /// The most recent pixel snapshot received for each canvas layer, blitted into overlapping
+    /// tiles by the workers.
+    canvas_snapshots: HashMap<LayerId, CanvasBlob>,
+
+    /// In-flight snapshot requests, one per canvas layer, polled without blocking so the paint
+    /// critical path never waits on the canvas task.
+    canvas_snapshot_ports: HashMap<LayerId, Receiver<Vec<u8>>>,
+
+    /// The number of consecutive readbacks for which a canvas layer's pixels were unchanged from
+    /// the previous snapshot. Drives the request backoff below.
+    canvas_unchanged_streaks: HashMap<LayerId, u32>,
+
+    /// The number of future `canvas_blob_for_layer` calls, per canvas layer, to skip requesting a
+    /// fresh readback for. Set once a layer's `canvas_unchanged_streaks` crosses the backoff
+    /// threshold, so a canvas that has stopped changing stops round-tripping to the canvas task on
+    /// every single paint.
+    canvas_request_backoff: HashMap<LayerId, u32>,
+}
+
+/// The number of consecutive identical canvas readbacks required before a layer starts backing
+/// off on requesting new ones.
+const CANVAS_UNCHANGED_STREAK_BEFORE_BACKOFF: u32 = 3;
+
+/// The largest number of paints a canvas layer's readback request can be backed off for, even if
+/// its unchanged streak is longer; bounds how stale a snapshot can get once the canvas resumes
+/// changing.
+const CANVAS_MAX_REQUEST_BACKOFF: u32 = 8;
+
+/// A cached pixel snapshot of a canvas layer. Canvas output is treated as a "blob" source and
+/// blitted into the tiles it overlaps within the normal worker pipeline.
+#[derive(Clone)]
+struct CanvasBlob {
+    /// The RGBA pixels of the canvas at `size`.
+    pixels: Arc<Vec<u8>>,
+    /// The pixel dimensions of the snapshot.
+    size: Size2D<i32>,
+}
+
+/// A tile's page-space rectangle reduced to integer app-unit bounds, so it can key a hash map
+/// (`Rect<f32>` is neither `Eq` nor `Hash`). Ordered as origin x/y, size width/height.
+type PageRectKey = (i32, i32, i32, i32);
+
+/// Identifies a tile within the paint task's content-age cache: the owning layer plus the tile's
+/// page-space rectangle, as the request specified.
+type TileCacheKey = (LayerId, PageRectKey);
+
+/// What the cache remembers for a painted tile: the `content_age` that decides whether a repaint
+/// is needed, and the tile's screen rectangle. The screen rectangle lets `Msg::UnusedBuffer`
+/// evict the exact returned tile rather than every same-page-rect entry, since a returned
+/// `LayerBuffer` does not carry its `LayerId`.
+struct TileCacheEntry {
+    content_age: usize,
+    screen_pos: Rect<usize>,
+    /// The resolution the tile was painted at. A page rect keys the cache, but an unchanged tile
+    /// re-requested at a new zoom must still repaint, so the scale is part of the skip decision.
+    resolution: f32,
+}
+
+/// Reduces a page-space rectangle to its integer app-unit key.
+fn page_rect_key(rect: &Rect<f32>) -> PageRectKey {
+    (Au::from_f32_px(rect.origin.x).0,
+     Au::from_f32_px(rect.origin.y).0,
+     Au::from_f32_px(rect.size.width).0,
+     Au::from_f32_px(rect.size.height).0)
 }
 
 // If we implement this as a function, we get borrowck errors from borrowing
@@ -180,9 +266,9 @@ impl<C> PaintTask<C> where C: PaintListener + Send + 'static {
                 let mut compositor = compositor;
                 let native_graphics_context = compositor.graphics_metadata().map(
                     |md| NativePaintingGraphicsContext::from_metadata(&md));
-                let worker_threads = WorkerThreadProxy::spawn(compositor.graphics_metadata(),
-                                                              font_cache_task,
-                                                              time_profiler_chan.clone());
+                let worker_threads = WorkerThreadPool::spawn(compositor.graphics_metadata(),
+                                                             font_cache_task,
+                                                             time_profiler_chan.clone());
 
                 // Register this thread as a memory reporter, via its own channel.
                 let reporter = box chan.clone();
@@ -205,9 +291,14 @@ impl<C> PaintTask<C> where C: PaintListener + Send + 'static {
                     paint_permission: false,
                     current_epoch: None,
                     buffer_map: BufferMap::new(10000000),
-                    worker_threads: worker_threads,
                     used_buffer_count: 0,
-                    canvas_map: HashMap::new()
+                    worker_threads: worker_threads,
+                    canvas_map: HashMap::new(),
+                    tile_cache: HashMap::new(),
+                    canvas_snapshots: HashMap::new(),
+                    canvas_snapshot_ports: HashMap::new(),
+                    canvas_unchanged_streaks: HashMap::new(),
+                    canvas_request_backoff: HashMap::new(),
                 };
 
                 paint_task.start();
@@ -219,9 +310,7 @@ impl<C> PaintTask<C> where C: PaintListener + Send + 'static {
                 }
 
                 // Tell all the worker threads to shut down.
-                for worker_thread in paint_task.worker_threads.iter_mut() {
-                    worker_thread.exit()
-                }
+                paint_task.worker_threads.exit();
             }
 
             debug!("paint_task: shutdown_chan send");
@@ -232,11 +321,16 @@ impl<C> PaintTask<C> where C: PaintListener + Send + 'static {
     fn start(&mut self) {
         debug!("PaintTask: beginning painting loop");
 
-        let mut exit_response_channel : Option<Sender<()>> = None;
+        let mut exit_response_channel: Option<Sender<()>> = None;
         let mut waiting_for_compositor_buffers_to_exit = false;
         loop {
             match self.port.recv().unwrap() {
                 Msg::PaintInit(epoch, stacking_context) => {
+                    // A new stacking context means all cached tile geometry is stale, so drop the
+                    // content-age cache whenever the epoch advances.
+                    if self.current_epoch != Some(epoch) {
+                        self.tile_cache.clear();
+                    }
                     self.current_epoch = Some(epoch);
                     self.root_stacking_context = Some(stacking_context.clone());
 
@@ -247,7 +341,7 @@ impl<C> PaintTask<C> where C: PaintListener + Send + 'static {
                         continue;
                     }
 
-                    // If waiting to exit, ignore any more paint commands
+                    // If waiting to exit, ignore any more paint commands.
                     if waiting_for_compositor_buffers_to_exit {
                         continue;
                     }
@@ -267,7 +361,7 @@ impl<C> PaintTask<C> where C: PaintListener + Send + 'static {
                         continue;
                     }
 
-                    // If waiting to exit, ignore any more paint commands
+                    // If waiting to exit, ignore any more paint commands.
                     if waiting_for_compositor_buffers_to_exit {
                         continue;
                     }
@@ -282,9 +376,11 @@ impl<C> PaintTask<C> where C: PaintListener + Send + 'static {
                         }
                     }
 
+                    // These buffers are now loaned to the compositor until it returns them via
+                    // `Msg::UnusedBuffer`; count them so exit can wait for their return.
                     for reply in replies.iter() {
                         let &(_, ref buffer_set) = reply;
-                        self.used_buffer_count += (*buffer_set).buffers.len();
+                        self.used_buffer_count += buffer_set.buffers.len();
                     }
 
                     debug!("PaintTask: returning surfaces");
@@ -297,10 +393,37 @@ impl<C> PaintTask<C> where C: PaintListener + Send + 'static {
                     debug!("PaintTask {:?}: Received {} unused buffers", self.id, unused_buffers.len());
                     self.used_buffer_count -= unused_buffers.len();
 
+                    // The compositor no longer holds these tiles, so drop their content-age cache
+                    // entries: a subsequent request for the same region must repaint rather than
+                    // being skipped into a blank tile. A returned buffer carries no `LayerId`, so
+                    // match on its page rect *and* screen rect together; that pair identifies the
+                    // one tile that was returned without evicting a same-page-rect entry belonging
+                    // to a different layer.
+                    let returned: Vec<(PageRectKey, Rect<usize>)> =
+                        unused_buffers.iter()
+                                      .map(|buffer| (page_rect_key(&buffer.rect), buffer.screen_pos))
+                                      .collect();
+                    let stale: Vec<TileCacheKey> =
+                        self.tile_cache.iter()
+                            .filter(|&(key, entry)| {
+                                returned.iter().any(|&(page_key, screen_pos)| {
+                                    key.1 == page_key && entry.screen_pos == screen_pos
+                                })
+                            })
+                            .map(|(key, _)| *key)
+                            .collect();
+                    for key in stale.into_iter() {
+                        self.tile_cache.remove(&key);
+                    }
+
+                    // Reclaim the returned surfaces into our buffer map so the next paint can reuse
+                    // them instead of allocating fresh native surfaces.
                     for buffer in unused_buffers.into_iter().rev() {
                         self.buffer_map.insert(native_graphics_context!(self), buffer);
                     }
 
+                    // If we were waiting on these buffers to exit, and they have all come back, we
+                    // can release them and shut down cleanly.
                     if waiting_for_compositor_buffers_to_exit && self.used_buffer_count == 0 {
                         debug!("PaintTask: Received all loaned buffers, exiting.");
                         exit_response_channel.map(|channel| channel.send(()));
@@ -330,16 +453,14 @@ impl<C> PaintTask<C> where C: PaintListener + Send + 'static {
                     let msg = mem::ProfilerMsg::UnregisterReporter(self.reporter_name.clone());
                     self.mem_profiler_chan.send(msg);
 
-                    // Ask the compositor to return any used buffers it
-                    // is holding for this paint task. This previously was
-                    // sent from the constellation. However, it needs to be sent
-                    // from here to avoid a race condition with the paint
-                    // messages above.
+                    // Ask the compositor to return any used buffers it is holding for this paint
+                    // task. This is sent from here, rather than the constellation, to avoid a race
+                    // with the paint messages above.
                     self.compositor.notify_paint_task_exiting(self.id);
 
                     let should_wait_for_compositor_buffers = match exit_type {
                         PipelineExitType::Complete => false,
-                        PipelineExitType::PipelineOnly => self.used_buffer_count != 0
+                        PipelineExitType::PipelineOnly => self.used_buffer_count != 0,
                     };
 
                     if !should_wait_for_compositor_buffers {
@@ -348,10 +469,12 @@ impl<C> PaintTask<C> where C: PaintListener + Send + 'static {
                         break;
                     }
 
-                    // If we own buffers in the compositor and we are not exiting completely, wait
-                    // for the compositor to return buffers, so that we can release them properly.
-                    // When doing a complete exit, the compositor lets all buffers leak.
-                    debug!("PaintTask {:?}: Saw ExitMsg, {} buffers in use", self.id, self.used_buffer_count);
+                    // We own buffers in the compositor and are not exiting completely, so wait for
+                    // the compositor to return them before dropping the graphics context. A full
+                    // exit lets the compositor leak all buffers instead.
+                    debug!("PaintTask {:?}: Saw ExitMsg, {} buffers in use",
+                           self.id,
+                           self.used_buffer_count);
                     waiting_for_compositor_buffers_to_exit = true;
                     exit_response_channel = response_channel;
                 }
@@ -361,14 +484,22 @@ impl<C> PaintTask<C> where C: PaintListener + Send + 'static {
 
     /// Retrieves an appropriately-sized layer buffer from the cache to match the requirements of
     /// the given tile, or creates one if a suitable one cannot be found.
+    ///
+    /// This only serves the CPU path: Azure has no entry point to rebind a recycled native surface
+    /// as a different `DrawTarget`'s FBO backing (only `new_with_fbo`, which always allocates), so
+    /// the GPU path cannot make use of a buffer-map hit and does not consult it. On the GPU path
+    /// `AzurePaintBackend::finish_tile` builds its own fresh `LayerBuffer` once the tile is painted.
     fn find_or_create_layer_buffer_for_tile(&mut self, tile: &BufferRequest, scale: f32)
                                             -> Option<Box<LayerBuffer>> {
         let width = tile.screen_rect.size.width;
         let height = tile.screen_rect.size.height;
+
         if opts::get().gpu_painting {
             return None
         }
 
+        // Reuse a returned surface of this size from our buffer map if we have one, rather than
+        // allocating a fresh native surface.
         match self.buffer_map.find(tile.screen_rect.size) {
             Some(mut buffer) => {
                 buffer.rect = tile.page_rect;
@@ -401,6 +532,80 @@ impl<C> PaintTask<C> where C: PaintListener + Send + 'static {
         })
     }
 
+    /// Returns the most recent pixel snapshot of the canvas backing `layer_id`, if any, and
+    /// ensures a fresh snapshot request is in flight for the next paint unless the canvas is
+    /// backing off (see below). Requests are made asynchronously: the current (possibly one frame
+    /// stale) snapshot is returned immediately rather than round-tripping to the canvas task on
+    /// the paint critical path. Returns `None` when the layer is not a canvas layer, or when it is
+    /// one but no snapshot has arrived yet.
+    fn canvas_blob_for_layer(&mut self, layer_id: LayerId, size: Size2D<i32>)
+                             -> Option<CanvasBlob> {
+        if !self.canvas_map.contains_key(&layer_id) {
+            return None
+        }
+
+        // Harvest a completed snapshot, if one arrived since the last paint. The readback returns
+        // the raw pixel buffer; its dimensions are the canvas layer's device size, computed by the
+        // caller from the stacking context. That computed size can be stale by the time the
+        // readback lands (e.g. the canvas was resized between request and reply), so verify the
+        // buffer actually holds `size` worth of RGBA pixels before trusting it — an `Image` built
+        // from a mismatched size/byte-length pair would read out of bounds when blitted. Leave the
+        // request in flight and return the previous snapshot if it hasn't finished yet.
+        match self.canvas_snapshot_ports.get(&layer_id).map(|port| port.try_recv()) {
+            Some(Ok(pixels)) => {
+                let expected_len = size.width as usize * size.height as usize * 4;
+                if pixels.len() == expected_len {
+                    // A layout `content_age` can't tell us whether the canvas's pixels actually
+                    // changed (that's the whole reason canvas layers skip the `tile_cache`), but
+                    // comparing this readback against the previous one can: track how many in a
+                    // row came back identical, so a canvas that has stopped being drawn to can
+                    // back off from polling it every paint instead of forever round-tripping to
+                    // the canvas task for the same bytes.
+                    let unchanged = self.canvas_snapshots.get(&layer_id)
+                        .map_or(false, |previous| previous.size == size && *previous.pixels == pixels);
+                    let streak = self.canvas_unchanged_streaks.entry(layer_id).or_insert(0);
+                    *streak = if unchanged { *streak + 1 } else { 0 };
+                    self.canvas_snapshots.insert(layer_id, CanvasBlob {
+                        pixels: Arc::new(pixels),
+                        size: size,
+                    });
+                } else {
+                    debug!("canvas readback for layer {:?} was {} bytes, expected {} for {:?}; \
+                            keeping the previous snapshot", layer_id, pixels.len(), expected_len, size);
+                }
+            }
+            Some(Err(TryRecvError::Empty)) => return self.canvas_snapshots.get(&layer_id).cloned(),
+            Some(Err(TryRecvError::Disconnected)) | None => {}
+        }
+        self.canvas_snapshot_ports.remove(&layer_id);
+
+        // If this canvas is backing off, count down and skip the round-trip this time.
+        let backoff = *self.canvas_request_backoff.get(&layer_id).unwrap_or(&0);
+        if backoff > 0 {
+            self.canvas_request_backoff.insert(layer_id, backoff - 1);
+            return self.canvas_snapshots.get(&layer_id).cloned()
+        }
+
+        // Once pixels have come back unchanged several times in a row, assume the canvas has gone
+        // quiet and wait out a streak-scaled number of paints before asking again; a real redraw
+        // shows up at most that many paints late, which is an acceptable trade for not hammering
+        // the canvas task with readbacks of unchanging content.
+        let streak = *self.canvas_unchanged_streaks.get(&layer_id).unwrap_or(&0);
+        if streak >= CANVAS_UNCHANGED_STREAK_BEFORE_BACKOFF {
+            self.canvas_request_backoff.insert(layer_id, streak.min(CANVAS_MAX_REQUEST_BACKOFF));
+        }
+
+        // Fire a fresh asynchronous request for use on the next paint.
+        let (sender, receiver) = channel();
+        if let Some(renderer) = self.canvas_map.get(&layer_id) {
+            let msg = CanvasMsg::FromLayout(FromLayoutMsg::SendPixelContents(sender));
+            let _ = renderer.lock().unwrap().send(msg);
+        }
+        self.canvas_snapshot_ports.insert(layer_id, receiver);
+
+        self.canvas_snapshots.get(&layer_id).cloned()
+    }
+
     /// Paints one layer and places the painted tiles in `replies`.
     fn paint(&mut self,
               replies: &mut Vec<(LayerId, Box<LayerBufferSet>)>,
@@ -420,24 +625,74 @@ impl<C> PaintTask<C> where C: PaintListener + Send + 'static {
                 return
             };
 
-            // Divide up the layer into tiles and distribute them to workers via a simple round-
-            // robin strategy.
+            // If this layer is backed by a canvas, grab its latest pixel snapshot (and kick off an
+            // asynchronous request for the next one) so the workers can blit it into the tiles. The
+            // snapshot's pixel dimensions are the layer's device size: its page-space bounds scaled
+            // by the current resolution.
+            let canvas_size = Size2D::new((stacking_context.bounds.size.width.to_f32_px() * scale) as i32,
+                                          (stacking_context.bounds.size.height.to_f32_px() * scale) as i32);
+            let canvas_blob = self.canvas_blob_for_layer(layer_id, canvas_size);
+
+            // A canvas layer's `content_age` tracks its layout display list, not the pixels a
+            // script paints into the canvas via 2D/WebGL calls; an animating canvas can leave a
+            // tile's age untouched forever. Skip the content-age cache for canvas-backed layers
+            // so their tiles keep re-blitting the latest snapshot from `canvas_blob_for_layer`.
+            let is_canvas_layer = self.canvas_map.contains_key(&layer_id);
+
+            // Push every tile that needs painting onto the shared work queue; idle workers steal
+            // the next available job rather than being assigned one up front, so a worker that
+            // draws several cheap tiles keeps pulling work while another grinds on an expensive
+            // one. Tiles whose content has not aged since the last paint are skipped entirely,
+            // leaving the compositor's existing tile in place. This `tile_cache` is the single
+            // place content-age skipping is decided; the workers paint every tile they are handed.
+            //
+            // Won't-do: a separate per-worker repaint-skip keyed on content age, checked again
+            // after a tile is dispatched to its worker. A second skip decision made at that point
+            // would only re-derive the answer this `tile_cache` check already gives before the
+            // tile is ever queued, so no such second check exists.
             let tiles = std_mem::replace(&mut tiles, Vec::new());
-            let tile_count = tiles.len();
-            for (i, tile) in tiles.into_iter().enumerate() {
-                let thread_id = i % self.worker_threads.len();
+            let mut jobs = Vec::with_capacity(tiles.len());
+            for tile in tiles.into_iter() {
+                let key = (layer_id, page_rect_key(&tile.page_rect));
+                if !is_canvas_layer &&
+                   self.tile_cache.get(&key)
+                       .map(|entry| (entry.content_age, entry.resolution))
+                        == Some((tile.content_age, scale)) {
+                    continue;
+                }
+
                 let layer_buffer = self.find_or_create_layer_buffer_for_tile(&tile, scale);
-                self.worker_threads[thread_id].paint_tile(thread_id,
-                                                          tile,
-                                                          layer_buffer,
-                                                          stacking_context.clone(),
-                                                          scale,
-                                                          layer_kind);
+                jobs.push(PaintJob {
+                    tile: tile,
+                    layer_buffer: layer_buffer,
+                    stacking_context: stacking_context.clone(),
+                    scale: scale,
+                    layer_kind: layer_kind,
+                    canvas_blob: canvas_blob.clone(),
+                });
+            }
+
+            // Paint every dispatched tile in parallel on the worker pool; the results come back in
+            // arbitrary order, but each `LayerBuffer` carries its own `screen_pos`/`rect`.
+            let new_buffers = self.worker_threads.paint_tiles(jobs);
+
+            // Record the content age we actually painted for each tile, so a later paint with the
+            // same age can skip it. Done after painting rather than before dispatch so a tile is
+            // never recorded as painted unless a buffer for it was produced.
+            for buffer in new_buffers.iter() {
+                self.tile_cache.insert((layer_id, page_rect_key(&buffer.rect)),
+                                       TileCacheEntry {
+                                           content_age: buffer.content_age,
+                                           screen_pos: buffer.screen_pos,
+                                           resolution: buffer.resolution,
+                                       });
+            }
+
+            // Every requested tile was unchanged; leave the compositor's tiles untouched rather
+            // than handing it an empty buffer set.
+            if new_buffers.is_empty() {
+                return
             }
-            let new_buffers = (0..tile_count).map(|i| {
-                let thread_id = i % self.worker_threads.len();
-                self.worker_threads[thread_id].get_painted_tile_buffer()
-            }).collect();
 
             let layer_buffer_set = box LayerBufferSet {
                 buffers: new_buffers,
@@ -524,138 +779,209 @@ impl<C> PaintTask<C> where C: PaintListener + Send + 'static {
     }
 }
 
-struct WorkerThreadProxy {
-    sender: Sender<MsgToWorkerThread>,
-    receiver: Receiver<MsgFromWorkerThread>,
+/// A single tile's worth of work to be painted by a worker thread.
+///
+/// Won't-do: off-thread rasterization of expensive blob display items (e.g. large SVG or
+/// image-set content) ahead of the tile paint cycle. Tiles already paint off the paint task's own
+/// thread via `WorkerThreadPool`, but a blob item is still rasterized inline as part of whichever
+/// tile's `optimize_and_draw_into_context` call reaches it. Doing better needs a blob display-item
+/// type and a rasterize-request hook in the display-list traversal, and `display_list` (where that
+/// traversal and `StackingContext` live) is a module outside this file; declining rather than
+/// building around a type this file cannot add to. `canvas_blob_for_layer` is a separate, simpler
+/// mechanism — snapshotting an externally-rendered canvas — and does not cover this.
+struct PaintJob {
+    tile: BufferRequest,
+    layer_buffer: Option<Box<LayerBuffer>>,
+    stacking_context: Arc<StackingContext>,
+    scale: f32,
+    layer_kind: LayerKind,
+    canvas_blob: Option<CanvasBlob>,
+}
+
+/// A message posted to the shared job queue that every worker thread pulls from.
+enum WorkerMsg {
+    Paint(PaintJob, Sender<Box<LayerBuffer>>),
+    Exit,
+}
+
+/// A pool of worker threads fed by a single shared work queue. Jobs are pushed onto the queue and
+/// stolen by whichever worker next becomes idle, so tiles of uneven cost don't stall the layer.
+/// Each thread builds its painting state (the `FontContext` and native GL context) once, when it
+/// starts, and reuses it for every tile it paints rather than recreating it per tile.
+struct WorkerThreadPool {
+    /// The shared job queue and the condvar used to wake a worker when a job is pushed.
+    queue: Arc<(Mutex<VecDeque<WorkerMsg>>, Condvar)>,
+    /// The number of worker threads, used to broadcast the exit signal.
+    thread_count: usize,
 }
 
-impl WorkerThreadProxy {
+impl WorkerThreadPool {
     fn spawn(native_graphics_metadata: Option<NativeGraphicsMetadata>,
              font_cache_task: FontCacheTask,
              time_profiler_chan: time::ProfilerChan)
-             -> Vec<WorkerThreadProxy> {
+             -> WorkerThreadPool {
         let thread_count = if opts::get().gpu_painting {
             1
         } else {
             opts::get().paint_threads
         };
-        (0..thread_count).map(|_| {
-            let (from_worker_sender, from_worker_receiver) = channel();
-            let (to_worker_sender, to_worker_receiver) = channel();
+
+        let queue = Arc::new((Mutex::new(VecDeque::new()), Condvar::new()));
+
+        for thread_id in 0..thread_count {
+            let queue = queue.clone();
             let native_graphics_metadata = native_graphics_metadata.clone();
             let font_cache_task = font_cache_task.clone();
             let time_profiler_chan = time_profiler_chan.clone();
-            spawn_named("PaintWorker".to_owned(), move || {
-                let mut worker_thread = WorkerThread::new(from_worker_sender,
-                                                          to_worker_receiver,
-                                                          native_graphics_metadata,
-                                                          font_cache_task,
-                                                          time_profiler_chan);
-                worker_thread.main();
+            spawn_named(format!("PaintWorker {}", thread_id), move || {
+                let mut worker = WorkerThread::new(thread_id,
+                                                   native_graphics_metadata,
+                                                   font_cache_task,
+                                                   time_profiler_chan);
+                let &(ref lock, ref condvar) = &*queue;
+                loop {
+                    // Steal the next job off the shared queue, releasing the lock before painting
+                    // so the other workers can grab work while this one is busy.
+                    let msg = {
+                        let mut jobs = lock.lock().unwrap();
+                        while jobs.is_empty() {
+                            jobs = condvar.wait(jobs).unwrap();
+                        }
+                        jobs.pop_front().unwrap()
+                    };
+                    match msg {
+                        WorkerMsg::Exit => break,
+                        WorkerMsg::Paint(job, result_sender) => {
+                            let _ = result_sender.send(worker.paint_job(job));
+                        }
+                    }
+                }
             });
-            WorkerThreadProxy {
-                receiver: from_worker_receiver,
-                sender: to_worker_sender,
+        }
+
+        WorkerThreadPool {
+            queue: queue,
+            thread_count: thread_count,
+        }
+    }
+
+    /// Paints every job on the pool and returns the painted buffers once all of them have
+    /// finished. Ordering is not preserved, but each `LayerBuffer` carries its own
+    /// `screen_pos`/`rect`.
+    fn paint_tiles(&self, jobs: Vec<PaintJob>) -> Vec<Box<LayerBuffer>> {
+        let job_count = jobs.len();
+        let (result_sender, result_receiver) = channel();
+
+        {
+            let &(ref lock, ref condvar) = &*self.queue;
+            let mut queue = lock.lock().unwrap();
+            for job in jobs.into_iter() {
+                queue.push_back(WorkerMsg::Paint(job, result_sender.clone()));
             }
-        }).collect()
+            condvar.notify_all();
+        }
+
+        (0..job_count).map(|_| result_receiver.recv().unwrap()).collect()
     }
 
-    fn paint_tile(&mut self,
-                  thread_id: usize,
-                  tile: BufferRequest,
-                  layer_buffer: Option<Box<LayerBuffer>>,
-                  stacking_context: Arc<StackingContext>,
-                  scale: f32,
-                  layer_kind: LayerKind) {
-        let msg = MsgToWorkerThread::PaintTile(thread_id,
-                                               tile,
-                                               layer_buffer,
-                                               stacking_context,
-                                               scale,
-                                               layer_kind);
-        self.sender.send(msg).unwrap()
+    fn exit(&self) {
+        let &(ref lock, ref condvar) = &*self.queue;
+        let mut queue = lock.lock().unwrap();
+        for _ in 0..self.thread_count {
+            queue.push_back(WorkerMsg::Exit);
+        }
+        condvar.notify_all();
     }
+}
+
+/// The maximum number of draw targets retained per tile size. Bounds each size bucket of the
+/// reuse free-list; the number of distinct size buckets is not capped.
+const DRAW_TARGET_POOL_LIMIT: usize = 16;
+
+/// A size-bucketed free-list of `DrawTarget`s that a worker reuses across tiles instead of
+/// allocating a fresh surface for every paint. During steady-state scrolling tile sizes are
+/// uniform, so this turns most cache hits into a clear-and-repaint rather than an allocation.
+struct DrawTargetPool {
+    free_lists: HashMap<(i32, i32), Vec<DrawTarget>>,
+}
 
-    fn get_painted_tile_buffer(&mut self) -> Box<LayerBuffer> {
-        match self.receiver.recv().unwrap() {
-            MsgFromWorkerThread::PaintedTile(layer_buffer) => layer_buffer,
+impl DrawTargetPool {
+    fn new() -> DrawTargetPool {
+        DrawTargetPool {
+            free_lists: HashMap::new(),
         }
     }
 
-    fn exit(&mut self) {
-        self.sender.send(MsgToWorkerThread::Exit).unwrap()
+    /// Checks out a previously-submitted target of the given size, if one is cached. Every pooled
+    /// target is allocated in the `B8G8R8A8` format the workers paint in, so the size is enough to
+    /// key the free-list and no format check is needed.
+    fn checkout(&mut self, size: Size2D<i32>) -> Option<DrawTarget> {
+        self.free_lists.get_mut(&(size.width, size.height)).and_then(|list| list.pop())
+    }
+
+    /// Returns a submitted target of the given size to the free-list, capping each per-size bucket
+    /// at `DRAW_TARGET_POOL_LIMIT`; targets beyond the cap are dropped.
+    fn give_back(&mut self, size: Size2D<i32>, target: DrawTarget) {
+        let list = self.free_lists.entry((size.width, size.height)).or_insert_with(Vec::new);
+        if list.len() < DRAW_TARGET_POOL_LIMIT {
+            list.push(target)
+        }
     }
 }
 
 struct WorkerThread {
-    sender: Sender<MsgFromWorkerThread>,
-    receiver: Receiver<MsgToWorkerThread>,
+    /// This worker's index in the pool; used to tint tiles in debug paint modes.
+    id: usize,
     native_graphics_context: Option<NativePaintingGraphicsContext>,
     font_context: Box<FontContext>,
     time_profiler_sender: time::ProfilerChan,
+    /// The rasterization backend this worker paints through.
+    backend: Box<PaintBackend>,
 }
 
 impl WorkerThread {
-    fn new(sender: Sender<MsgFromWorkerThread>,
-           receiver: Receiver<MsgToWorkerThread>,
+    fn new(id: usize,
            native_graphics_metadata: Option<NativeGraphicsMetadata>,
            font_cache_task: FontCacheTask,
            time_profiler_sender: time::ProfilerChan)
            -> WorkerThread {
         WorkerThread {
-            sender: sender,
-            receiver: receiver,
+            id: id,
             native_graphics_context: native_graphics_metadata.map(|metadata| {
                 NativePaintingGraphicsContext::from_metadata(&metadata)
             }),
             font_context: box FontContext::new(font_cache_task.clone()),
             time_profiler_sender: time_profiler_sender,
+            backend: PaintBackend::select(),
         }
     }
 
-    fn main(&mut self) {
-        loop {
-            match self.receiver.recv().unwrap() {
-                MsgToWorkerThread::Exit => break,
-                MsgToWorkerThread::PaintTile(thread_id, tile, layer_buffer, stacking_context, scale, layer_kind) => {
-                    let draw_target = self.optimize_and_paint_tile(thread_id,
-                                                                   &tile,
-                                                                   stacking_context,
-                                                                   scale,
-                                                                   layer_kind);
-                    let buffer = self.create_layer_buffer_for_painted_tile(&tile,
-                                                                           layer_buffer,
-                                                                           draw_target,
-                                                                           scale);
-                    self.sender.send(MsgFromWorkerThread::PaintedTile(buffer)).unwrap()
-                }
-            }
-        }
+
+    /// Paints a single job. Every tile handed to a worker is painted; content-age skipping is
+    /// decided once, in the paint task's `tile_cache`, before the job is ever dispatched.
+    fn paint_job(&mut self, job: PaintJob) -> Box<LayerBuffer> {
+        let draw_target = self.optimize_and_paint_tile(&job.tile,
+                                                       job.stacking_context,
+                                                       job.scale,
+                                                       job.layer_kind,
+                                                       job.canvas_blob);
+        self.create_layer_buffer_for_painted_tile(&job.tile,
+                                                  job.layer_buffer,
+                                                  draw_target,
+                                                  job.scale)
     }
 
     fn optimize_and_paint_tile(&mut self,
-                               thread_id: usize,
                                tile: &BufferRequest,
                                stacking_context: Arc<StackingContext>,
                                scale: f32,
-                               layer_kind: LayerKind)
+                               layer_kind: LayerKind,
+                               canvas_blob: Option<CanvasBlob>)
                                -> DrawTarget {
         let size = Size2D::new(tile.screen_rect.size.width as i32, tile.screen_rect.size.height as i32);
-        let draw_target = if !opts::get().gpu_painting {
-            DrawTarget::new(BackendType::Skia, size, SurfaceFormat::B8G8R8A8)
-        } else {
-            // FIXME(pcwalton): Cache the components of draw targets (texture color buffer,
-            // paintbuffers) instead of recreating them.
-            let native_graphics_context =
-                native_graphics_context!(self) as *const _ as SkiaGrGLNativeContextRef;
-            let draw_target = DrawTarget::new_with_fbo(BackendType::Skia,
-                                                       native_graphics_context,
-                                                       size,
-                                                       SurfaceFormat::B8G8R8A8);
-
-            draw_target.make_current();
-            draw_target
-        };
+        // Ask the active backend for a render target of this size. For the Azure CPU path this is
+        // a (possibly recycled) `DrawTarget`; the GPU path always allocates a fresh FBO-backed one.
+        let draw_target = self.backend.create_render_target(size, self.native_graphics_context.as_ref());
 
         {
             // Build the paint context.
@@ -685,7 +1011,9 @@ impl WorkerThread {
             // Clear the buffer.
             paint_context.clear();
 
-            // Draw the display list.
+            // Draw the display list, measuring how long this tile took so the debug HUD can
+            // report it.
+            let paint_start = Instant::now();
             time::profile(time::ProfilerCategory::PaintingPerTile,
                           None,
                           self.time_profiler_sender.clone(),
@@ -696,15 +1024,43 @@ impl WorkerThread {
                                                                 None);
                 paint_context.draw_target.flush();
                     });
+            let paint_time = paint_start.elapsed();
+
+            // The canvas fills its layer starting at the layer's page origin. Blit the cached
+            // snapshot only into tiles that actually overlap the canvas rect, rather than into
+            // every tile of the layer, and position it by this tile's own page offset and scale.
+            if let Some(ref blob) = canvas_blob {
+                let canvas_page_rect = Rect::new(Point2D::new(0.0, 0.0),
+                                                 Size2D::new(blob.size.width as f32 / scale,
+                                                             blob.size.height as f32 / scale));
+                if canvas_page_rect.intersects(&tile.page_rect) {
+                    let origin = Point2D::new(Au::from_f32_px(-tile.page_rect.origin.x * scale),
+                                              Au::from_f32_px(-tile.page_rect.origin.y * scale));
+                    let canvas_size = Size2D::new(Au::from_px(blob.size.width),
+                                                  Au::from_px(blob.size.height));
+                    let canvas_rect = Rect::new(origin, canvas_size);
+                    let image = Arc::new(Image {
+                        width: blob.size.width as u32,
+                        height: blob.size.height as u32,
+                        format: PixelFormat::RGBA8,
+                        bytes: (*blob.pixels).clone(),
+                    });
+                    paint_context.draw_image(&canvas_rect,
+                                             &canvas_size,
+                                             image,
+                                             image_rendering::T::Auto);
+                }
+            }
 
             if opts::get().show_debug_parallel_paint {
-                // Overlay a transparent solid color to identify the thread that
-                // painted this tile.
-                let color = THREAD_TINT_COLORS[thread_id % THREAD_TINT_COLORS.len()];
+                // Overlay a transparent solid color to identify the thread that painted this tile,
+                // then draw a small HUD in its top-left corner with the details of the paint.
+                let color = THREAD_TINT_COLORS[self.id % THREAD_TINT_COLORS.len()];
                 paint_context.draw_solid_color(&Rect::new(Point2D::new(Au(0), Au(0)),
                                                           Size2D::new(Au::from_px(size.width),
                                                                       Au::from_px(size.height))),
                                                color);
+                draw_tile_debug_hud(&mut paint_context, self.id, tile, paint_time, color);
             }
             if opts::get().paint_flashing {
                 // Overlay a random transparent color.
@@ -725,18 +1081,120 @@ impl WorkerThread {
                                             draw_target: DrawTarget,
                                             scale: f32)
                                             -> Box<LayerBuffer> {
+        // Hand the painted target off to the backend, which extracts (or uploads) its surface
+        // into the `LayerBuffer` that the compositor will display.
+        self.backend.finish_tile(tile,
+                                 layer_buffer,
+                                 draw_target,
+                                 scale,
+                                 self.native_graphics_context.as_ref())
+    }
+}
+
+/// A pluggable rasterization backend. Tiles are painted into a `DrawTarget` by the shared
+/// `PaintContext`; the backend decides how that target is allocated (CPU surface or GL FBO) and
+/// how its pixels are handed to the compositor's `LayerBuffer`.
+trait PaintBackend {
+    /// Creates — or recycles from an internal pool — a render target of the given size. For the
+    /// GL path the returned target is made current before it is returned. Azure exposes no entry
+    /// point to rebind a recycled native surface as a different target's FBO backing, so the GL
+    /// path always allocates a fresh one here.
+    fn create_render_target(&mut self,
+                            size: Size2D<i32>,
+                            native_graphics_context: Option<&NativePaintingGraphicsContext>)
+                            -> DrawTarget;
+
+    /// Finalizes a painted tile: reads back or hands off the target's surface into `layer_buffer`
+    /// (allocating one if `None`) and returns the buffer ready for compositing.
+    fn finish_tile(&mut self,
+                   tile: &BufferRequest,
+                   layer_buffer: Option<Box<LayerBuffer>>,
+                   draw_target: DrawTarget,
+                   scale: f32,
+                   native_graphics_context: Option<&NativePaintingGraphicsContext>)
+                   -> Box<LayerBuffer>;
+}
+
+impl PaintBackend {
+    /// Selects the rasterization backend for this worker.
+    ///
+    /// Won't-do: a wgpu backend alongside Azure. It would need the `wgpu` crate (not a dependency
+    /// of this crate, and there is no manifest in this tree to add it to) and a surface/swapchain
+    /// integration with the compositor's native graphics context, which does not exist here either.
+    /// `AzurePaintBackend` is the only backend; `PaintBackend` stays a trait,
+    /// rather than being folded into a concrete struct, purely so a real second backend can be
+    /// added later without touching the worker paint loop.
+    fn select() -> Box<PaintBackend> {
+        box AzurePaintBackend::new()
+    }
+}
+
+/// The default backend, painting through Azure into a Skia-backed CPU surface or GL FBO.
+struct AzurePaintBackend {
+    /// A free-list of CPU draw targets bucketed by tile size, reused across tiles.
+    draw_target_pool: DrawTargetPool,
+}
+
+impl AzurePaintBackend {
+    fn new() -> AzurePaintBackend {
+        AzurePaintBackend {
+            draw_target_pool: DrawTargetPool::new(),
+        }
+    }
+}
+
+impl PaintBackend for AzurePaintBackend {
+    fn create_render_target(&mut self,
+                            size: Size2D<i32>,
+                            native_graphics_context: Option<&NativePaintingGraphicsContext>)
+                            -> DrawTarget {
+        if !opts::get().gpu_painting {
+            // Reuse a target of this size from the free-list if we have one; otherwise allocate.
+            // The reused target's pixels are cleared by the caller via `paint_context.clear()`.
+            return match self.draw_target_pool.checkout(size) {
+                Some(draw_target) => draw_target,
+                None => DrawTarget::new(BackendType::Skia, size, SurfaceFormat::B8G8R8A8),
+            }
+        }
+
+        // GPU path. These Azure bindings offer no way to rebind a recycled native surface as a new
+        // target's FBO backing (only `new_with_fbo`, which always allocates), so a fresh
+        // FBO-backed target is allocated every tile; recycled surfaces are left untouched in the
+        // paint task's buffer map for the CPU path to reuse.
+        let context = native_graphics_context.expect("Need a graphics context to do GPU painting");
+        let native_graphics_context = context as *const _ as SkiaGrGLNativeContextRef;
+        let draw_target = DrawTarget::new_with_fbo(BackendType::Skia,
+                                                   native_graphics_context,
+                                                   size,
+                                                   SurfaceFormat::B8G8R8A8);
+        draw_target.make_current();
+        draw_target
+    }
+
+    fn finish_tile(&mut self,
+                   tile: &BufferRequest,
+                   layer_buffer: Option<Box<LayerBuffer>>,
+                   draw_target: DrawTarget,
+                   scale: f32,
+                   native_graphics_context: Option<&NativePaintingGraphicsContext>)
+                   -> Box<LayerBuffer> {
         // Extract the texture from the draw target and place it into its slot in the buffer. If
-        // using CPU painting, upload it first.
-        //
-        // FIXME(pcwalton): We should supply the texture and native surface *to* the draw target in
-        // GPU painting mode, so that it doesn't have to recreate it.
+        // using CPU painting, upload it first; on the GPU path we steal the draw target's backing
+        // surface out into the `LayerBuffer`.
         if !opts::get().gpu_painting {
+            let context = native_graphics_context.expect("Need a graphics context to upload");
             let mut buffer = layer_buffer.unwrap();
             draw_target.snapshot().get_data_surface().with_data(|data| {
-                buffer.native_surface.upload(native_graphics_context!(self), data);
+                buffer.native_surface.upload(context, data);
                 debug!("painting worker thread uploading to native surface {}",
                        buffer.native_surface.get_id());
             });
+
+            // The pixels now live in the native surface, so the draw target is free to be reused
+            // by a subsequent tile of the same size.
+            let size = Size2D::new(tile.screen_rect.size.width as i32,
+                                   tile.screen_rect.size.height as i32);
+            self.draw_target_pool.give_back(size, draw_target);
             return buffer
         }
 
@@ -761,13 +1219,86 @@ impl WorkerThread {
     }
 }
 
-enum MsgToWorkerThread {
-    Exit,
-    PaintTile(usize, BufferRequest, Option<Box<LayerBuffer>>, Arc<StackingContext>, f32, LayerKind),
+/// The maximum number of tally ticks a HUD row draws before giving up and showing a single
+/// "overflow" tick instead; bounds the strip to a fixed width regardless of the value.
+const MAX_HUD_TALLY_TICKS: i32 = 24;
+
+/// Draws one HUD row as tally ticks: one tick per unit of `value`, up to `MAX_HUD_TALLY_TICKS`, so
+/// the exact value can be read off by counting rather than having to gauge a bar's length. Values
+/// beyond the cap collapse to a single wide tick spanning the whole row.
+fn draw_hud_tally_row(paint_context: &mut PaintContext, y: i32, value: i32, color: Color) {
+    const TICK_WIDTH: i32 = 2;
+    const TICK_GAP: i32 = 1;
+    const BAR_HEIGHT: i32 = 4;
+
+    if value > MAX_HUD_TALLY_TICKS {
+        let width = MAX_HUD_TALLY_TICKS * (TICK_WIDTH + TICK_GAP) - TICK_GAP;
+        paint_context.draw_solid_color(&Rect::new(Point2D::new(Au::from_px(1), Au::from_px(y)),
+                                                  Size2D::new(Au::from_px(width),
+                                                              Au::from_px(BAR_HEIGHT))),
+                                       color);
+        return
+    }
+
+    for tick in 0..value.max(0) {
+        let x = 1 + tick * (TICK_WIDTH + TICK_GAP);
+        paint_context.draw_solid_color(&Rect::new(Point2D::new(Au::from_px(x), Au::from_px(y)),
+                                                  Size2D::new(Au::from_px(TICK_WIDTH),
+                                                              Au::from_px(BAR_HEIGHT))),
+                                       color);
+    }
 }
 
-enum MsgFromWorkerThread {
-    PaintedTile(Box<LayerBuffer>),
+/// Draws the per-tile debug HUD into the top-left corner of the tile.
+///
+/// Won't-do: legible numerals via `PaintContext`'s `font_context`. `font_context.rs` and
+/// `paint_context.rs` both live outside this file, and `PaintContext` exposes no text-drawing
+/// method in what this file can see of it. This HUD draws five rows over a dark backing strip,
+/// tinted with the worker's thread color, in place of numerals:
+/// - worker id and content age are exact counts, so they are drawn as tally ticks — one tick per
+///   unit, capped at `MAX_HUD_TALLY_TICKS` — and can be read precisely by counting. The worker
+///   row draws `worker_id + 1` ticks, so worker 0 still shows one tick.
+/// - tile origin x, origin y, and paint time are continuous quantities; they remain coarse
+///   proportional bars (1px per 8 device pixels of origin, 1px per ms, all clamped to the strip
+///   width)
+///   and are only a relative-magnitude indicator between tiles, not an exact readout.
+fn draw_tile_debug_hud(paint_context: &mut PaintContext,
+                       worker_id: usize,
+                       tile: &BufferRequest,
+                       paint_time: Duration,
+                       color: Color) {
+    let paint_ms = paint_time.as_secs() as f64 * 1000.0 +
+                   paint_time.subsec_nanos() as f64 / 1_000_000.0;
+
+    const BAR_HEIGHT: i32 = 4;
+    const BAR_GAP: i32 = 2;
+    const STRIP_WIDTH: i32 = 72;
+    const ROW_COUNT: i32 = 5;
+    let strip_height = ROW_COUNT * (BAR_HEIGHT + BAR_GAP) + BAR_GAP;
+    let backing = Color { r: 0.0, g: 0.0, b: 0.0, a: 0.5 };
+    paint_context.draw_solid_color(&Rect::new(Point2D::new(Au(0), Au(0)),
+                                              Size2D::new(Au::from_px(STRIP_WIDTH),
+                                                          Au::from_px(strip_height))),
+                                   backing);
+
+    let row_y = |row: i32| BAR_GAP + row * (BAR_HEIGHT + BAR_GAP);
+
+    // Row 0: worker id, row 1: content age — exact, countable tally ticks.
+    draw_hud_tally_row(paint_context, row_y(0), worker_id as i32 + 1, color);
+    draw_hud_tally_row(paint_context, row_y(1), tile.content_age as i32, color);
+
+    // Row 2: tile origin x, row 3: tile origin y, row 4: paint time — coarse proportional bars.
+    let proportional_metrics = [tile.screen_rect.origin.x as i32 / 8,
+                                tile.screen_rect.origin.y as i32 / 8,
+                                paint_ms as i32];
+    for (index, &value) in proportional_metrics.iter().enumerate() {
+        let length = if value < 1 { 1 } else if value > STRIP_WIDTH - 2 { STRIP_WIDTH - 2 } else { value };
+        let y = row_y(2 + index as i32);
+        paint_context.draw_solid_color(&Rect::new(Point2D::new(Au::from_px(1), Au::from_px(y)),
+                                                  Size2D::new(Au::from_px(length),
+                                                              Au::from_px(BAR_HEIGHT))),
+                                       color);
+    }
 }
 
 pub static THREAD_TINT_COLORS: [Color; 8] = [